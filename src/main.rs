@@ -1,18 +1,45 @@
 use crossterm::{
-    event::{self, DisableMouseCapture, EnableMouseCapture, Event, KeyCode},
+    cursor,
+    event::{
+        self, DisableMouseCapture, EnableMouseCapture, Event, KeyCode, MouseButton, MouseEventKind,
+    },
     execute,
     terminal::{disable_raw_mode, enable_raw_mode, EnterAlternateScreen, LeaveAlternateScreen},
 };
 use std::{error::Error, io};
 use tui::{
     backend::{Backend, CrosstermBackend},
+    buffer::Buffer,
     layout::{Constraint, Direction, Layout, Rect},
     style::{Color, Modifier, Style},
-    widgets::{Block, Row, Table, TableState},
+    text::Spans,
+    widgets::{Block, Borders, Paragraph, StatefulWidget, Tabs, Widget, Wrap},
     Frame, Terminal,
 };
 
+const TAB_TITLES: [&str; 3] = ["Game", "Rules", "New Game"];
+
+/// Restores the terminal to its normal state. Shared between the happy
+/// path in `main` and the panic hook below, which needs to run the same
+/// sequence before a panic's backtrace gets printed to a still-raw,
+/// still-alternate-screen terminal.
+fn restore_terminal() {
+    let _ = disable_raw_mode();
+    let _ = execute!(
+        io::stdout(),
+        LeaveAlternateScreen,
+        DisableMouseCapture,
+        cursor::Show
+    );
+}
+
 fn main() -> Result<(), Box<dyn Error>> {
+    let default_hook = std::panic::take_hook();
+    std::panic::set_hook(Box::new(move |panic_info| {
+        restore_terminal();
+        default_hook(panic_info);
+    }));
+
     // setup terminal
     enable_raw_mode()?;
     let mut stdout = io::stdout();
@@ -24,13 +51,7 @@ fn main() -> Result<(), Box<dyn Error>> {
     let res = run_app(&mut terminal);
 
     // restore terminal
-    disable_raw_mode()?;
-    execute!(
-        terminal.backend_mut(),
-        LeaveAlternateScreen,
-        DisableMouseCapture
-    )?;
-    terminal.show_cursor()?;
+    restore_terminal();
 
     if let Err(err) = res {
         println!("{:?}", err)
@@ -39,51 +60,298 @@ fn main() -> Result<(), Box<dyn Error>> {
     Ok(())
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 enum Player {
     One,
     Two,
 }
 
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Outcome {
+    Winner(Player),
+    Draw,
+}
+
+/// The two persistent screens the top `Tabs` bar can show. "New Game" is
+/// not a screen of its own: it's a menu action that resets the game and
+/// drops the player back onto `Game`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Screen {
+    Game,
+    Rules,
+}
+
+impl Screen {
+    fn tab_index(self) -> usize {
+        match self {
+            Screen::Game => 0,
+            Screen::Rules => 1,
+        }
+    }
+}
+
 #[derive(Debug, Clone)]
 struct GameState {
+    screen: Screen,
     active_player: Player,
-    selected_cell: TableState,
+    board: [[Option<Player>; 6]; 6],
+    cursor: (usize, usize),
     can_place: bool,
     can_turn: bool,
+    // Set once the player has picked which quadrant to rotate; the next
+    // rotate-direction key then applies to this quadrant and ends the turn.
+    selected_quadrant: Option<usize>,
+    outcome: Option<Outcome>,
+    // Geometry from the last draw, kept around so a mouse event (which
+    // arrives after the frame that produced it) can be hit-tested against
+    // the same area the board and quadrant controls were rendered into.
+    board_rect: Rect,
+    quadrant_controls: Vec<QuadrantControl>,
 }
 
 impl GameState {
     fn new() -> Self {
         Self {
+            screen: Screen::Game,
             active_player: Player::One,
-            selected_cell: TableState::default(),
+            board: [[None; 6]; 6],
+            cursor: (0, 0),
             can_place: true,
-            can_turn: true,
+            can_turn: false,
+            selected_quadrant: None,
+            outcome: None,
+            board_rect: Rect::default(),
+            quadrant_controls: Vec::new(),
+        }
+    }
+
+    fn cell_at(&self, row: usize, col: usize) -> Option<Player> {
+        self.board[row][col]
+    }
+
+    /// Moves the top tab bar selection left or right, wrapping around.
+    /// Landing on "New Game" (index 2) isn't a screen, it resets the
+    /// game and returns to `Screen::Game`.
+    fn cycle_tab(&mut self, forward: bool) {
+        let tab_count = TAB_TITLES.len();
+        let current = self.screen.tab_index();
+        let next = if forward {
+            (current + 1) % tab_count
+        } else {
+            (current + tab_count - 1) % tab_count
+        };
+
+        match next {
+            0 => self.screen = Screen::Game,
+            1 => self.screen = Screen::Rules,
+            _ => *self = GameState::new(),
+        }
+    }
+
+    fn place_at_cursor(&mut self) {
+        if !self.can_place {
+            return;
+        }
+
+        let (row, col) = self.cursor;
+        if self.board[row][col].is_some() {
+            return;
+        }
+
+        self.board[row][col] = Some(self.active_player);
+        self.can_place = false;
+        self.can_turn = true;
+    }
+
+    fn choose_quadrant(&mut self, quadrant: usize) {
+        if self.can_turn {
+            self.selected_quadrant = Some(quadrant);
+        }
+    }
+
+    fn rotate_selected_quadrant(&mut self, clockwise: bool) {
+        let quadrant = match self.selected_quadrant {
+            Some(quadrant) => quadrant,
+            None => return,
+        };
+
+        rotate_quadrant(&mut self.board, quadrant, clockwise);
+        self.outcome = check_outcome(&self.board);
+
+        self.active_player = match self.active_player {
+            Player::One => Player::Two,
+            Player::Two => Player::One,
+        };
+        self.can_place = true;
+        self.can_turn = false;
+        self.selected_quadrant = None;
+    }
+}
+
+/// Scans the board for any five-in-a-row, in any of the four directions,
+/// after a quadrant rotation. A rotation can complete a line for both
+/// players at once, in which case the game is a draw; likewise if the
+/// board fills up with no line at all.
+fn check_outcome(board: &[[Option<Player>; 6]; 6]) -> Option<Outcome> {
+    const DIRECTIONS: [(isize, isize); 4] = [(0, 1), (1, 0), (1, 1), (1, -1)];
+
+    let mut one_wins = false;
+    let mut two_wins = false;
+
+    for row in 0..6isize {
+        for col in 0..6isize {
+            for (row_step, col_step) in DIRECTIONS {
+                let end_row = row + row_step * 4;
+                let end_col = col + col_step * 4;
+                if !(0..6).contains(&end_row) || !(0..6).contains(&end_col) {
+                    continue;
+                }
+
+                let player = match board[row as usize][col as usize] {
+                    Some(player) => player,
+                    None => continue,
+                };
+
+                let five_in_a_row = (1..5).all(|step| {
+                    let r = (row + row_step * step) as usize;
+                    let c = (col + col_step * step) as usize;
+                    board[r][c] == Some(player)
+                });
+
+                if five_in_a_row {
+                    match player {
+                        Player::One => one_wins = true,
+                        Player::Two => two_wins = true,
+                    }
+                }
+            }
+        }
+    }
+
+    match (one_wins, two_wins) {
+        (true, true) => Some(Outcome::Draw),
+        (true, false) => Some(Outcome::Winner(Player::One)),
+        (false, true) => Some(Outcome::Winner(Player::Two)),
+        (false, false) if board.iter().flatten().all(Option::is_some) => Some(Outcome::Draw),
+        (false, false) => None,
+    }
+}
+
+/// Rotates one of the board's four 3x3 quadrants in place.
+///
+/// `quadrant` is 0=top-left, 1=top-right, 2=bottom-left, 3=bottom-right.
+fn rotate_quadrant(board: &mut [[Option<Player>; 6]; 6], quadrant: usize, clockwise: bool) {
+    let (row_off, col_off) = match quadrant {
+        0 => (0, 0),
+        1 => (0, 3),
+        2 => (3, 0),
+        3 => (3, 3),
+        _ => return,
+    };
+
+    let mut cells = [[None; 3]; 3];
+    for r in 0..3 {
+        for c in 0..3 {
+            cells[r][c] = board[row_off + r][col_off + c];
+        }
+    }
+
+    let rotated = if clockwise {
+        // Transpose then reverse each row.
+        let mut transposed = [[None; 3]; 3];
+        for r in 0..3 {
+            for c in 0..3 {
+                transposed[r][c] = cells[c][r];
+            }
+        }
+        for row in transposed.iter_mut() {
+            row.reverse();
+        }
+        transposed
+    } else {
+        // Reverse each row then transpose.
+        let mut reversed = cells;
+        for row in reversed.iter_mut() {
+            row.reverse();
+        }
+        let mut transposed = [[None; 3]; 3];
+        for r in 0..3 {
+            for c in 0..3 {
+                transposed[r][c] = reversed[c][r];
+            }
+        }
+        transposed
+    };
+
+    for r in 0..3 {
+        for c in 0..3 {
+            board[row_off + r][col_off + c] = rotated[r][c];
         }
     }
 }
 
 fn run_app<B: Backend>(terminal: &mut Terminal<B>) -> io::Result<()> {
     let mut state = GameState::new();
-    state.selected_cell.select(Some(0));
 
     loop {
         terminal.draw(|f| ui(f, &mut state))?;
 
-        if let Event::Key(key) = event::read()? {
-            let movement = match key.code {
+        match event::read()? {
+            Event::Key(key) => match key.code {
                 KeyCode::Char('q') => return Ok(()),
-                KeyCode::Char('w') => -7,
-                KeyCode::Char('a') => -1,
-                KeyCode::Char('s') => 1,
-                KeyCode::Char('d') => 7,
-                _ => 0,
-            };
+                // Tab navigation is always available, separate from
+                // whatever the active screen does with its own keys.
+                KeyCode::Left => state.cycle_tab(false),
+                KeyCode::Right => state.cycle_tab(true),
+                _ if state.outcome.is_some() => {}
+                _ if state.screen == Screen::Game => match key.code {
+                    KeyCode::Char('w') => state.cursor.0 = state.cursor.0.saturating_sub(1),
+                    KeyCode::Char('s') => state.cursor.0 = (state.cursor.0 + 1).min(5),
+                    KeyCode::Char('a') => state.cursor.1 = state.cursor.1.saturating_sub(1),
+                    KeyCode::Char('d') => state.cursor.1 = (state.cursor.1 + 1).min(5),
+                    KeyCode::Enter | KeyCode::Char(' ') => state.place_at_cursor(),
+                    KeyCode::Char('1') => state.choose_quadrant(0),
+                    KeyCode::Char('2') => state.choose_quadrant(1),
+                    KeyCode::Char('3') => state.choose_quadrant(2),
+                    KeyCode::Char('4') => state.choose_quadrant(3),
+                    KeyCode::Char('e') => state.rotate_selected_quadrant(true),
+                    KeyCode::Char('r') => state.rotate_selected_quadrant(false),
+                    _ => {}
+                },
+                _ => {}
+            },
+            Event::Mouse(mouse) => {
+                if state.screen != Screen::Game || state.outcome.is_some() {
+                    continue;
+                }
+                if mouse.kind != MouseEventKind::Down(MouseButton::Left) {
+                    continue;
+                }
 
-            state.selected_cell.select(Some(
-                ((state.selected_cell.selected().unwrap() as i32 + movement) % 49) as usize,
-            ));
+                match (state.can_place, state.can_turn) {
+                    (true, _) => {
+                        if let Some(cell) =
+                            cell_at_position(state.board_rect, mouse.column, mouse.row)
+                        {
+                            state.cursor = cell;
+                            state.place_at_cursor();
+                        }
+                    }
+                    (false, true) => {
+                        if let Some(control) = state
+                            .quadrant_controls
+                            .iter()
+                            .find(|control| rect_contains(control.rect, mouse.column, mouse.row))
+                        {
+                            let (quadrant, clockwise) = (control.quadrant, control.clockwise);
+                            state.choose_quadrant(quadrant);
+                            state.rotate_selected_quadrant(clockwise);
+                        }
+                    }
+                    (false, false) => {}
+                }
+            }
+            _ => {}
         }
     }
 }
@@ -91,11 +359,48 @@ fn run_app<B: Backend>(terminal: &mut Terminal<B>) -> io::Result<()> {
 fn ui<B: Backend>(f: &mut Frame<B>, state: &mut GameState) {
     let frame_chunks = Layout::default()
         .direction(Direction::Vertical)
-        .constraints([Constraint::Percentage(10), Constraint::Percentage(90)].as_ref())
+        .constraints([Constraint::Length(3), Constraint::Min(0)].as_ref())
         .split(f.size());
 
-    top_bar_ui(f, frame_chunks[0], &state);
-    board_ui(f, frame_chunks[1], state);
+    tabs_ui(f, frame_chunks[0], state);
+
+    match state.screen {
+        Screen::Game => {
+            let game_chunks = Layout::default()
+                .direction(Direction::Vertical)
+                .constraints([Constraint::Percentage(10), Constraint::Percentage(90)].as_ref())
+                .split(frame_chunks[1]);
+
+            top_bar_ui(f, game_chunks[0], state);
+            board_ui(f, game_chunks[1], state);
+        }
+        Screen::Rules => rules_ui(f, frame_chunks[1]),
+    }
+}
+
+fn tabs_ui<B: Backend>(f: &mut Frame<B>, target: Rect, state: &GameState) {
+    let titles = TAB_TITLES.iter().map(|title| Spans::from(*title)).collect();
+
+    let tabs = Tabs::new(titles)
+        .block(Block::default().borders(Borders::ALL))
+        .select(state.screen.tab_index())
+        .highlight_style(Style::default().fg(Color::Yellow).add_modifier(Modifier::BOLD));
+
+    f.render_widget(tabs, target);
+}
+
+fn rules_ui<B: Backend>(f: &mut Frame<B>, target: Rect) {
+    let text = "Place your marble on any empty cell, then rotate one of the four \
+        3x3 quadrants clockwise or counterclockwise. The first player to line up \
+        five marbles in a row, column, or diagonal wins. If a rotation completes \
+        a line for both players at once, or the board fills up with no line, the \
+        game is a draw.";
+
+    let rules = Paragraph::new(text)
+        .block(Block::default().title("Rules").borders(Borders::ALL))
+        .wrap(Wrap { trim: true });
+
+    f.render_widget(rules, target);
 }
 
 fn top_bar_ui<B: Backend>(f: &mut Frame<B>, target: Rect, state: &GameState) {
@@ -116,49 +421,213 @@ fn top_bar_ui<B: Backend>(f: &mut Frame<B>, target: Rect, state: &GameState) {
     let active_player = Block::default().title(format!("{:?}", state.active_player));
     f.render_widget(active_player, top_bar_chunks[1]);
 
+    if let Some(outcome) = &state.outcome {
+        let message = match outcome {
+            Outcome::Winner(player) => format!("{:?} wins! (q to quit)", player),
+            Outcome::Draw => "Draw! (q to quit)".to_string(),
+        };
+        f.render_widget(Block::default().title(message), top_bar_chunks[2]);
+    }
+
     let place_label = Block::default().title(if state.can_place {
-        "Can place"
+        "Place a marble"
     } else {
         "Can't place"
     });
     f.render_widget(place_label, top_bar_chunks[3]);
 
-    let turn_label = Block::default().title(if state.can_turn {
-        "Can turn"
-    } else {
+    let turn_label = Block::default().title(if !state.can_turn {
         "Can't turn"
+    } else if state.selected_quadrant.is_none() {
+        "Choose a quadrant (1-4)"
+    } else {
+        "Rotate it (e/r)"
     });
     f.render_widget(turn_label, top_bar_chunks[4]);
 }
 
+/// A clickable rotate-quadrant control, as rendered below the board.
+#[derive(Debug, Clone, Copy)]
+struct QuadrantControl {
+    rect: Rect,
+    quadrant: usize,
+    clockwise: bool,
+}
+
+fn rect_contains(rect: Rect, x: u16, y: u16) -> bool {
+    x >= rect.x && x < rect.x + rect.width && y >= rect.y && y < rect.y + rect.height
+}
+
+/// Maps a click inside `board_rect` (the board widget's inner area, as
+/// stored by `board_ui`) back to the 6x6 cell it landed on, accounting
+/// for the quadrant separators the same way `Board::render` lays them out.
+fn cell_at_position(board_rect: Rect, x: u16, y: u16) -> Option<(usize, usize)> {
+    if !rect_contains(board_rect, x, y) {
+        return None;
+    }
+
+    let rel_x = x - board_rect.x;
+    let rel_y = y - board_rect.y;
+
+    let col = match rel_x {
+        0..=1 => 0,
+        2..=3 => 1,
+        4..=5 => 2,
+        7..=8 => 3,
+        9..=10 => 4,
+        11..=12 => 5,
+        _ => return None,
+    };
+
+    let row = match rel_y {
+        0..=2 => rel_y as usize,
+        4..=6 => rel_y as usize - 1,
+        _ => return None,
+    };
+
+    Some((row, col))
+}
+
 fn board_ui<B: Backend>(f: &mut Frame<B>, target: Rect, state: &mut GameState) {
-    let board = Table::new(vec![
-        Row::new([".", ".", ".", "|", ".", ".", "."]),
-        Row::new([".", ".", ".", "|", ".", ".", "."]),
-        Row::new([".", ".", ".", "|", ".", ".", "."]),
-        Row::new(["-", "-", "-", "|", "-", "-", "-"]),
-        Row::new([".", ".", ".", "|", ".", ".", "."]),
-        Row::new([".", ".", ".", "|", ".", ".", "."]),
-        Row::new([".", ".", ".", "|", ".", ".", "."]),
-    ])
-    // You can set the style of the entire Table.
-    .style(Style::default().fg(Color::White))
-    // As any other widget, a Table can be wrapped in a Block.
-    .block(Block::default().title("Board"))
-    // Columns widths are constrained in the same way as Layout...
-    .widths(&[
-        Constraint::Length(1),
-        Constraint::Length(1),
-        Constraint::Length(1),
-        Constraint::Length(1),
-        Constraint::Length(1),
-        Constraint::Length(1),
-        Constraint::Length(1),
-    ])
-    // ...and they can be separated by a fixed spacing.
-    .column_spacing(1)
-    // If you wish to highlight a row in any specific way when it is selected...
-    .highlight_style(Style::default().fg(Color::Blue));
-
-    f.render_stateful_widget(board, target, &mut state.selected_cell);
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Min(0), Constraint::Length(1)].as_ref())
+        .split(target);
+
+    let block = Block::default().title("Board");
+    state.board_rect = block.inner(chunks[0]);
+
+    let mut cells = [[None; 6]; 6];
+    for (row, cells_row) in cells.iter_mut().enumerate() {
+        for (col, cell) in cells_row.iter_mut().enumerate() {
+            *cell = state.cell_at(row, col);
+        }
+    }
+
+    let board = Board::new(cells).block(block);
+    f.render_stateful_widget(board, chunks[0], &mut state.cursor);
+
+    state.quadrant_controls = quadrant_controls_ui(f, chunks[1]);
+}
+
+/// Renders a row of `Q1 <> Q2 <> ...` rotate buttons below the board
+/// and returns the screen rect of each `<`/`>` so mouse clicks can be
+/// hit-tested against them.
+fn quadrant_controls_ui<B: Backend>(f: &mut Frame<B>, target: Rect) -> Vec<QuadrantControl> {
+    let mut controls = Vec::new();
+    let mut x = target.x;
+
+    for quadrant in 0..4 {
+        let label = format!("Q{} ", quadrant + 1);
+        if x + label.len() as u16 > target.x + target.width {
+            break;
+        }
+        f.render_widget(
+            Paragraph::new(label.as_str()),
+            Rect::new(x, target.y, label.len() as u16, 1),
+        );
+        x += label.len() as u16;
+
+        let ccw_rect = Rect::new(x, target.y, 1, 1);
+        f.render_widget(Paragraph::new("<"), ccw_rect);
+        controls.push(QuadrantControl {
+            rect: ccw_rect,
+            quadrant,
+            clockwise: false,
+        });
+        x += 1;
+
+        let cw_rect = Rect::new(x, target.y, 1, 1);
+        f.render_widget(Paragraph::new(">"), cw_rect);
+        controls.push(QuadrantControl {
+            rect: cw_rect,
+            quadrant,
+            clockwise: true,
+        });
+        x += 2; // trailing space between quadrant groups
+    }
+
+    controls
+}
+
+/// A 6x6 Pentago board rendered as four 3x3 quadrants with visible
+/// separators. The cursor is carried in `(row, col)` state the same way
+/// `TableState` carries a selected row for `Table`, so the selection
+/// survives between redraws.
+struct Board<'a> {
+    cells: [[Option<Player>; 6]; 6],
+    block: Option<Block<'a>>,
+}
+
+impl<'a> Board<'a> {
+    fn new(cells: [[Option<Player>; 6]; 6]) -> Self {
+        Self { cells, block: None }
+    }
+
+    fn block(mut self, block: Block<'a>) -> Self {
+        self.block = Some(block);
+        self
+    }
+}
+
+impl<'a> StatefulWidget for Board<'a> {
+    type State = (usize, usize);
+
+    fn render(mut self, area: Rect, buf: &mut Buffer, cursor: &mut Self::State) {
+        let board_area = match self.block.take() {
+            Some(block) => {
+                let inner = block.inner(area);
+                block.render(area, buf);
+                inner
+            }
+            None => area,
+        };
+
+        // Each cell is two columns wide (glyph + spacing) and one row tall,
+        // with an extra row/column inserted after the third row and the
+        // third column to draw the separator between quadrants.
+        for row in 0..6 {
+            let y_offset = if row >= 3 { 1 } else { 0 };
+            let y = board_area.y + row as u16 + y_offset;
+            if y >= board_area.y + board_area.height {
+                continue;
+            }
+
+            for col in 0..6 {
+                let x_offset = if col >= 3 { 1 } else { 0 };
+                let x = board_area.x + (col as u16 * 2) + x_offset;
+                if x >= board_area.x + board_area.width {
+                    continue;
+                }
+
+                let (glyph, mut style) = match self.cells[row][col] {
+                    Some(Player::One) => ("X", Style::default().fg(Color::Cyan)),
+                    Some(Player::Two) => ("O", Style::default().fg(Color::Red)),
+                    None => (".", Style::default().fg(Color::White)),
+                };
+
+                if *cursor == (row, col) {
+                    style = style.add_modifier(Modifier::REVERSED);
+                }
+
+                buf.set_string(x, y, glyph, style);
+            }
+        }
+
+        let separator_style = Style::default().fg(Color::DarkGray);
+
+        if board_area.height > 6 {
+            let sep_y = board_area.y + 3;
+            for x in board_area.x..board_area.x + board_area.width {
+                buf.set_string(x, sep_y, "-", separator_style);
+            }
+        }
+
+        if board_area.width > 6 {
+            let sep_x = board_area.x + 6;
+            for y in board_area.y..board_area.y + board_area.height {
+                buf.set_string(sep_x, y, "|", separator_style);
+            }
+        }
+    }
 }